@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::collections::TryReserveError;
+use std::hash::{BuildHasher, RandomState};
+
+/// Errors surfaced by `Cache`'s fallible, memory-bounded operations.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CacheError {
+    /// The underlying `HashMap` failed to reserve the requested capacity.
+    AllocFailed(TryReserveError),
+}
+
+impl From<TryReserveError> for CacheError {
+    fn from(err: TryReserveError) -> Self {
+        CacheError::AllocFailed(err)
+    }
+}
+
+pub struct Cache<K, V, S = RandomState> {
+    data: HashMap<K, (V, u64), S>,
+    max_size: usize,
+    clock: u64,
+}
+
+impl<K, V> Cache<K, V, RandomState>
+where
+    K: std::hash::Hash + Eq + Clone,
+{
+    pub fn new(max_size: usize) -> Self {
+        Cache {
+            data: HashMap::new(),
+            max_size,
+            clock: 0,
+        }
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: std::hash::Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    /// Builds a cache backed by a custom `BuildHasher`, e.g. a non-cryptographic
+    /// hasher for hot lookup paths where SipHash's DoS resistance isn't needed.
+    pub fn with_hasher(max_size: usize, hasher: S) -> Self {
+        Cache {
+            data: HashMap::with_hasher(hasher),
+            max_size,
+            clock: 0,
+        }
+    }
+
+    /// Breaking change: `get` now requires `&mut self` (it used to take
+    /// `&self`) because every read bumps the recency clock used for LRU
+    /// eviction. Callers that previously shared a `Cache` behind an
+    /// immutable reference (e.g. multiple concurrent readers) now need
+    /// exclusive access to read it too — typically by putting the `Cache`
+    /// behind a `Mutex` or giving each reader its own instance.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        let clock = self.clock;
+        match self.data.get_mut(key) {
+            Some((value, last_used)) => {
+                *last_used = clock;
+                Some(value)
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.clock += 1;
+        let clock = self.clock;
+
+        if !self.data.contains_key(&key) && self.data.len() >= self.max_size {
+            self.evict_lru();
+        }
+
+        self.data.insert(key, (value, clock));
+    }
+
+    /// Like `insert`, but surfaces an allocation failure as `Err` instead of
+    /// aborting, by reserving capacity up front with `HashMap::try_reserve`.
+    /// Returns the previous value for `key`, if any, on success.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CacheError> {
+        self.clock += 1;
+        let clock = self.clock;
+
+        if !self.data.contains_key(&key) && self.data.len() >= self.max_size {
+            self.evict_lru();
+        }
+
+        if !self.data.contains_key(&key) {
+            self.data.try_reserve(1)?;
+        }
+
+        Ok(self.data.insert(key, (value, clock)).map(|(value, _)| value))
+    }
+
+    /// Reserves capacity for at least `additional` more entries, surfacing an
+    /// allocation failure as `Err` rather than aborting.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), CacheError> {
+        self.data.try_reserve(additional)?;
+        Ok(())
+    }
+
+    /// Removes the least-recently-used entry. No-op if the cache is empty.
+    fn evict_lru(&mut self) {
+        let lru_key = self
+            .data
+            .iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(key, _)| key)
+            .cloned();
+
+        if let Some(key) = lru_key {
+            self.data.remove(&key);
+        }
+    }
+
+    /// Returns a view onto the slot for `key`, for get-or-compute access
+    /// without a separate `get` followed by `insert`. A hit refreshes
+    /// recency just like `get`; a miss evicts (if full) only once the
+    /// closure passed to `or_insert_with` actually runs.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        if self.data.contains_key(&key) {
+            Entry::Occupied(OccupiedEntry { cache: self, key })
+        } else {
+            Entry::Vacant(VacantEntry { cache: self, key })
+        }
+    }
+}
+
+/// A view into a single slot of a `Cache`, returned by `Cache::entry`.
+pub enum Entry<'a, K, V, S> {
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+pub struct OccupiedEntry<'a, K, V, S> {
+    cache: &'a mut Cache<K, V, S>,
+    key: K,
+}
+
+pub struct VacantEntry<'a, K, V, S> {
+    cache: &'a mut Cache<K, V, S>,
+    key: K,
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S>
+where
+    K: std::hash::Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    /// Ensures a value is present, inserting `default` on a miss.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Ensures a value is present, computing it from `f` on a miss. `f` runs
+    /// only if the entry is vacant.
+    pub fn or_insert_with<F>(self, f: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => {
+                entry.cache.clock += 1;
+                let clock = entry.cache.clock;
+                let (value, last_used) = entry
+                    .cache
+                    .data
+                    .get_mut(&entry.key)
+                    .expect("occupied entry must exist");
+                *last_used = clock;
+                value
+            }
+            Entry::Vacant(entry) => {
+                entry.cache.clock += 1;
+                let clock = entry.cache.clock;
+                if !entry.cache.data.contains_key(&entry.key)
+                    && entry.cache.data.len() >= entry.cache.max_size
+                {
+                    entry.cache.evict_lru();
+                }
+                let (value, _) = entry
+                    .cache
+                    .data
+                    .entry(entry.key)
+                    .or_insert_with(|| (f(), clock));
+                value
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_refreshes_recency_so_it_survives_eviction() {
+        let mut cache = Cache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        // "a" is now the least recently used; touch it so "b" becomes the LRU.
+        assert_eq!(cache.get(&"a"), Some(&1));
+        cache.insert("c", 3);
+
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"c"), Some(&3));
+    }
+
+    #[test]
+    fn updating_an_existing_key_does_not_evict() {
+        let mut cache = Cache::new(1);
+        cache.insert("a", 1);
+        cache.insert("a", 2);
+
+        assert_eq!(cache.get(&"a"), Some(&2));
+    }
+
+    #[derive(Default)]
+    struct ToyHasher(u64);
+
+    impl std::hash::Hasher for ToyHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = self.0.wrapping_mul(31).wrapping_add(byte as u64);
+            }
+        }
+
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn with_hasher_builds_a_working_cache_under_a_custom_hasher() {
+        let mut cache: Cache<&str, i32, std::hash::BuildHasherDefault<ToyHasher>> =
+            Cache::with_hasher(2, std::hash::BuildHasherDefault::default());
+
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"b"), Some(&2));
+    }
+
+    #[test]
+    fn try_insert_returns_ok_none_for_a_fresh_key() {
+        let mut cache = Cache::new(2);
+        assert_eq!(cache.try_insert("a", 1), Ok(None));
+        assert_eq!(cache.get(&"a"), Some(&1));
+    }
+
+    #[test]
+    fn try_insert_returns_previous_value_when_updating() {
+        let mut cache = Cache::new(2);
+        cache.try_insert("a", 1).unwrap();
+
+        assert_eq!(cache.try_insert("a", 2), Ok(Some(1)));
+        assert_eq!(cache.get(&"a"), Some(&2));
+    }
+
+    #[test]
+    fn try_reserve_succeeds_for_a_reasonable_amount() {
+        let mut cache: Cache<&str, i32> = Cache::new(4);
+        assert!(cache.try_reserve(4).is_ok());
+    }
+
+    #[test]
+    fn entry_or_insert_with_runs_closure_only_on_miss() {
+        let mut cache = Cache::new(2);
+        let mut calls = 0;
+
+        assert_eq!(*cache.entry("a").or_insert_with(|| { calls += 1; 1 }), 1);
+        assert_eq!(*cache.entry("a").or_insert_with(|| { calls += 1; 2 }), 1);
+
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn entry_hit_refreshes_recency_so_it_survives_eviction() {
+        let mut cache = Cache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        // "a" is now the least recently used; touch it via entry() so "b" becomes the LRU.
+        assert_eq!(*cache.entry("a").or_insert_with(|| 99), 1);
+        cache.insert("c", 3);
+
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"c"), Some(&3));
+    }
+}
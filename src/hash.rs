@@ -0,0 +1,48 @@
+/// FNV-1a offset basis extended to 128 bits, per the FNV reference parameters.
+const FNV_OFFSET_BASIS_128: u128 = 0x6c62272e07bb014262b821756295c58d;
+/// FNV-1a prime extended to 128 bits, per the FNV reference parameters.
+const FNV_PRIME_128: u128 = 0x0000000001000000000000000000013b;
+
+/// Computes a fixed, 128-bit FNV-1a digest of `bytes`.
+///
+/// Unlike `std`'s `SipHash`-based `DefaultHasher`, this algorithm and its
+/// constants are fully specified here, so the digest is stable across Rust
+/// versions, platforms, and process runs. This makes it safe to persist as a
+/// cache key (e.g. for an on-disk parse cache) or to use when deciding
+/// whether a reparse can be skipped.
+pub fn hash_content(bytes: &[u8]) -> u128 {
+    let mut hash = FNV_OFFSET_BASIS_128;
+    for &byte in bytes {
+        hash ^= byte as u128;
+        hash = hash.wrapping_mul(FNV_PRIME_128);
+    }
+    hash
+}
+
+/// Thin wrapper over `hash_content` for callers that only need 64 bits, e.g.
+/// in-memory lookup keys that don't need to survive across process runs.
+pub fn hash_string(s: &str) -> u64 {
+    hash_content(s.as_bytes()) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_content_of_empty_input_is_the_offset_basis() {
+        assert_eq!(hash_content(b""), FNV_OFFSET_BASIS_128);
+    }
+
+    #[test]
+    fn hash_content_pins_known_vectors() {
+        assert_eq!(hash_content(b"a"), 0xd228cb696f1a8caf78912b704e4a8964);
+        assert_eq!(hash_content(b"hello"), 0xe3e1efd54283d94f7081314b599d31b3);
+    }
+
+    #[test]
+    fn hash_string_pins_the_low_64_bits_of_hash_content() {
+        assert_eq!(hash_string("a"), 0x78912b704e4a8964);
+        assert_eq!(hash_string("a"), hash_content(b"a") as u64);
+    }
+}
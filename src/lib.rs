@@ -0,0 +1,4 @@
+//! Core data structures shared by the tree-sitter parsing/chunking pipeline.
+
+pub mod cache;
+pub mod hash;